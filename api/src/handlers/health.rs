@@ -0,0 +1,21 @@
+use axum::{extract::Extension, http::StatusCode, response::IntoResponse, Json};
+use serde_json::json;
+use std::sync::Arc;
+
+use crate::repositories::health::HealthCheckRepository;
+
+pub async fn health() -> impl IntoResponse {
+    (StatusCode::OK, Json(json!({ "status": "ok" })))
+}
+
+pub async fn health_db<T: HealthCheckRepository>(
+    Extension(repository): Extension<Arc<T>>,
+) -> impl IntoResponse {
+    match repository.check().await {
+        Ok(_) => (StatusCode::OK, Json(json!({ "status": "ok" }))),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({ "status": "unavailable", "error": e.to_string() })),
+        ),
+    }
+}
@@ -0,0 +1,45 @@
+use axum::{
+    extract::{Extension, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::repositories::label::LabelRepository;
+
+#[derive(Debug, Deserialize)]
+pub struct CreateLabel {
+    name: String,
+}
+
+pub async fn create_label<T: LabelRepository>(
+    Json(payload): Json<CreateLabel>,
+    Extension(repository): Extension<Arc<T>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let label = repository
+        .create(payload.name)
+        .await
+        .or(Err(StatusCode::NOT_FOUND))?;
+
+    Ok((StatusCode::CREATED, Json(label)))
+}
+
+pub async fn all_label<T: LabelRepository>(
+    Extension(repository): Extension<Arc<T>>,
+) -> impl IntoResponse {
+    let labels = repository.all().await.unwrap();
+    (StatusCode::OK, Json(labels))
+}
+
+pub async fn delete_label<T: LabelRepository>(
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Arc<T>>,
+) -> StatusCode {
+    repository
+        .delete(id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .unwrap_or(StatusCode::NOT_FOUND)
+}
@@ -0,0 +1,3 @@
+pub mod health;
+pub mod label;
+pub mod todo;
@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Extension, Path, Query},
+    http::StatusCode,
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Sse,
+    },
+    Json,
+};
+use futures::Stream;
+use std::{convert::Infallible, sync::Arc};
+use tokio::sync::broadcast::Sender;
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream},
+    StreamExt,
+};
+
+use crate::repositories::todo::{
+    BatchItemResult, BatchOps, CreateTodo, ListOptions, TodoEvent, TodoEventKind, TodoRepository, UpdateTodo,
+    UpsertOutcome,
+};
+
+pub async fn create_todo<T: TodoRepository>(
+    Json(payload): Json<CreateTodo>,
+    Extension(repository): Extension<Arc<T>>,
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let todo = repository
+        .create(payload)
+        .await
+        .or(Err(StatusCode::NOT_FOUND))?;
+
+    let _ = tx.send(TodoEvent {
+        kind: TodoEventKind::Created,
+        todo: Some(todo.clone()),
+        id: todo.id,
+    });
+
+    Ok((StatusCode::CREATED, Json(todo)))
+}
+
+pub async fn find_todo<T: TodoRepository>(
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Arc<T>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let todo = repository
+        .find(id)
+        .await
+        .or(Err(StatusCode::NOT_FOUND))?;
+
+    Ok((StatusCode::OK, Json(todo)))
+}
+
+pub async fn all_todo<T: TodoRepository>(
+    Query(opts): Query<ListOptions>,
+    Extension(repository): Extension<Arc<T>>,
+) -> impl IntoResponse {
+    let todo = repository.all(opts).await.unwrap();
+    (StatusCode::OK, Json(todo))
+}
+
+pub async fn upsert_todo<T: TodoRepository>(
+    Path(id): Path<i32>,
+    Json(payload): Json<CreateTodo>,
+    Extension(repository): Extension<Arc<T>>,
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let outcome = repository
+        .upsert(id, payload)
+        .await
+        .or(Err(StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    let kind = match outcome {
+        UpsertOutcome::Created(_) => TodoEventKind::Created,
+        UpsertOutcome::Updated(_) => TodoEventKind::Updated,
+    };
+    let todo = outcome.into_entity();
+
+    let _ = tx.send(TodoEvent {
+        kind,
+        todo: Some(todo.clone()),
+        id: todo.id,
+    });
+
+    Ok((StatusCode::OK, Json(todo)))
+}
+
+pub async fn update_todo<T: TodoRepository>(
+    Path(id): Path<i32>,
+    Json(payload): Json<UpdateTodo>,
+    Extension(repository): Extension<Arc<T>>,
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let todo = repository
+        .update(id, payload)
+        .await
+        .or(Err(StatusCode::NOT_FOUND))?;
+
+    let _ = tx.send(TodoEvent {
+        kind: TodoEventKind::Updated,
+        todo: Some(todo.clone()),
+        id: todo.id,
+    });
+
+    Ok((StatusCode::OK, Json(todo)))
+}
+
+pub async fn delete_todo<T: TodoRepository>(
+    Path(id): Path<i32>,
+    Extension(repository): Extension<Arc<T>>,
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> StatusCode {
+    let result = repository.delete(id).await;
+
+    if result.is_ok() {
+        let _ = tx.send(TodoEvent {
+            kind: TodoEventKind::Deleted,
+            todo: None,
+            id,
+        });
+    }
+
+    result
+        .map(|_| StatusCode::NO_CONTENT)
+        .unwrap_or(StatusCode::NOT_FOUND)
+}
+
+pub async fn batch_todo<T: TodoRepository>(
+    Json(ops): Json<BatchOps>,
+    Extension(repository): Extension<Arc<T>>,
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> Result<impl IntoResponse, StatusCode> {
+    let results = repository
+        .batch(ops)
+        .await
+        .or(Err(StatusCode::INTERNAL_SERVER_ERROR))?;
+
+    let created = results.created.iter().map(|r| (TodoEventKind::Created, r));
+    let updated = results.updated.iter().map(|r| (TodoEventKind::Updated, r));
+    for (kind, item) in created.chain(updated) {
+        if let BatchItemResult::Ok(todo) = item {
+            let _ = tx.send(TodoEvent { kind, todo: Some(todo.clone()), id: todo.id });
+        }
+    }
+    for item in &results.deleted {
+        if let BatchItemResult::Ok(id) = item {
+            let _ = tx.send(TodoEvent { kind: TodoEventKind::Deleted, todo: None, id: *id });
+        }
+    }
+
+    Ok((StatusCode::OK, Json(results)))
+}
+
+pub async fn todo_events(
+    Extension(tx): Extension<Sender<TodoEvent>>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let stream = BroadcastStream::new(tx.subscribe()).filter_map(|msg| match msg {
+        Ok(event) => Some(Ok(Event::default().json_data(&event).unwrap())),
+        Err(BroadcastStreamRecvError::Lagged(_)) => None,
+    });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
@@ -1,24 +1,30 @@
 mod handlers;
 mod repositories;
 
-use std::{env, net::SocketAddr, sync::Arc};
+use std::{env, net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::{
-    extract::Extension,
-    routing::{delete, get, post},
+    extract::{DefaultBodyLimit, Extension},
+    routing::{delete, get, post, put},
     Router,
 };
 use dotenv::dotenv;
 use handlers::{
+    health::{health, health_db},
     label::{all_label, create_label, delete_label},
-    todo::{all_todo, create_todo, delete_todo, find_todo, update_todo},
+    todo::{
+        all_todo, batch_todo, create_todo, delete_todo, find_todo, todo_events, update_todo,
+        upsert_todo,
+    },
 };
 use hyper::header::CONTENT_TYPE;
 use repositories::{
+    health::{HealthCheckRepository, HealthCheckRepositoryForDb},
     label::{LabelRepository, LabelRepositoryForDb},
-    todo::{TodoRepository, TodoRepositoryForDb},
+    todo::{TodoEvent, TodoRepository, TodoRepositoryForDb},
 };
-use sqlx::PgPool;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use tokio::sync::broadcast;
 use tower_http::cors::{Any, CorsLayer, Origin};
 
 #[tokio::main]
@@ -30,13 +36,17 @@ async fn main() {
 
     let database_url = &env::var("DATABASE_URL").expect("undefined [DATABASE_URL]");
     tracing::debug!("start connect database...");
-    let pool = PgPool::connect(database_url)
+    let pool = PgPoolOptions::new()
+        .max_connections(max_connections())
+        .acquire_timeout(Duration::from_secs(3))
+        .connect(database_url)
         .await
         .unwrap_or_else(|_| panic!("failed connect database. url: {}", database_url));
 
     let app = create_app(
         TodoRepositoryForDb::new(pool.clone()),
         LabelRepositoryForDb::new(pool.clone()),
+        HealthCheckRepositoryForDb::new(pool.clone()),
     );
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
     tracing::debug!("listening on {}", addr);
@@ -47,18 +57,24 @@ async fn main() {
         .unwrap();
 }
 
-fn create_app<Todo: TodoRepository, Label: LabelRepository>(
+fn create_app<Todo: TodoRepository, Label: LabelRepository, Health: HealthCheckRepository>(
     todo_repository: Todo,
     label_repository: Label,
+    health_repository: Health,
 ) -> Router {
     Router::new()
         .route("/", get(root))
+        .route("/health", get(health))
+        .route("/health/db", get(health_db::<Health>))
         .route("/todos", post(create_todo::<Todo>).get(all_todo::<Todo>))
+        .route("/todos/events", get(todo_events))
+        .route("/todos/batch", post(batch_todo::<Todo>))
         .route(
             "/todos/:id",
             get(find_todo::<Todo>)
                 .delete(delete_todo::<Todo>)
-                .patch(update_todo::<Todo>),
+                .patch(update_todo::<Todo>)
+                .put(upsert_todo::<Todo>),
         )
         .route(
             "/labels",
@@ -67,22 +83,47 @@ fn create_app<Todo: TodoRepository, Label: LabelRepository>(
         .route("/labels/:id", delete(delete_label::<Label>))
         .layer(Extension(Arc::new(todo_repository)))
         .layer(Extension(Arc::new(label_repository)))
+        .layer(Extension(Arc::new(health_repository)))
+        .layer(Extension(broadcast::channel::<TodoEvent>(100).0))
         .layer(
             CorsLayer::new()
                 .allow_origin(Origin::exact("http://localhost:3001".parse().unwrap()))
                 .allow_methods(Any)
                 .allow_headers(vec![CONTENT_TYPE]),
         )
+        .layer(DefaultBodyLimit::max(max_body_bytes()))
 }
 
 async fn root() -> &'static str {
     "Hello, world!"
 }
 
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+
+fn max_connections() -> u32 {
+    env::var("DATABASE_MAX_CONNECTIONS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_else(|| {
+            let cpus = std::thread::available_parallelism()
+                .map(|n| n.get() as u32)
+                .unwrap_or(1);
+            cpus * 2
+        })
+}
+
+fn max_body_bytes() -> usize {
+    env::var("MAX_BODY_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::repositories::{
+        health::test_utils::HealthCheckRepositoryForMemory,
         label::{test_utils::LabelRepositoryForMemory, Label},
         todo::{test_utils::TodoRepositoryForMemory, CreateTodo, TodoEntity},
     };
@@ -152,6 +193,7 @@ mod test {
         let res = create_app(
             TodoRepositoryForMemory::new(labels),
             LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
         )
         .oneshot(req)
         .await
@@ -171,7 +213,11 @@ mod test {
             .await
             .expect("failed find todo");
         let req = build_req_with_empty(Method::GET, "/todos/1");
-        let res = create_app(todo_repository, LabelRepositoryForMemory::new())
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
@@ -193,7 +239,11 @@ mod test {
             .await
             .expect("failed get all todos");
         let req = build_req_with_empty(Method::GET, "/todos");
-        let res = create_app(todo_repository, LabelRepositoryForMemory::new())
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
@@ -225,7 +275,11 @@ mod test {
             }"#
             .to_string(),
         );
-        let res = create_app(todo_repository, LabelRepositoryForMemory::new())
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
@@ -242,7 +296,11 @@ mod test {
             .await
             .expect("failed delete todo");
         let req = build_req_with_empty(Method::DELETE, "/todos/1");
-        let res = create_app(todo_repository, LabelRepositoryForMemory::new())
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
@@ -262,6 +320,7 @@ mod test {
         let res = create_app(
             TodoRepositoryForMemory::new(labels),
             LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
         )
         .oneshot(req)
         .await
@@ -280,7 +339,11 @@ mod test {
             .await
             .expect("failed get all labels");
         let req = build_req_with_empty(Method::GET, "/labels");
-        let res = create_app(TodoRepositoryForMemory::new(vec![label]), label_repository)
+        let res = create_app(
+            TodoRepositoryForMemory::new(vec![label]),
+            label_repository,
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
@@ -300,10 +363,296 @@ mod test {
             .await
             .expect("failed delete label");
         let req = build_req_with_empty(Method::DELETE, "/labels/1");
-        let res = create_app(TodoRepositoryForMemory::new(vec![label]), label_repository)
+        let res = create_app(
+            TodoRepositoryForMemory::new(vec![label]),
+            label_repository,
+            HealthCheckRepositoryForMemory::new(),
+        )
             .oneshot(req)
             .await
             .unwrap();
         assert_eq!(StatusCode::NO_CONTENT, res.status());
     }
+
+    #[tokio::test]
+    async fn should_list_todos_with_pagination_and_label_filter() {
+        let (labels, label_ids) = label_fixture();
+        let other_label = Label::new(888, "other label".to_string());
+        let mut all_labels = labels.clone();
+        all_labels.push(other_label.clone());
+
+        let todo_repository = TodoRepositoryForMemory::new(all_labels);
+        todo_repository
+            .create(CreateTodo::new("first".to_string(), label_ids.clone()))
+            .await
+            .expect("failed create first todo");
+        todo_repository
+            .create(CreateTodo::new("second".to_string(), label_ids.clone()))
+            .await
+            .expect("failed create second todo");
+        todo_repository
+            .create(CreateTodo::new("third".to_string(), vec![other_label.id]))
+            .await
+            .expect("failed create third todo");
+
+        let req = build_req_with_empty(Method::GET, "/todos?label_id=999");
+        let res = create_app(
+            todo_repository.clone(),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let body: String = String::from_utf8(bytes.to_vec()).unwrap();
+        let todos: Vec<TodoEntity> = serde_json::from_str(&body)
+            .unwrap_or_else(|_| panic!("cannot convert Todo instance. body: {}", body));
+        assert_eq!(todos.len(), 2);
+
+        let req = build_req_with_empty(Method::GET, "/todos?limit=1&offset=1");
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let body: String = String::from_utf8(bytes.to_vec()).unwrap();
+        let todos: Vec<TodoEntity> = serde_json::from_str(&body)
+            .unwrap_or_else(|_| panic!("cannot convert Todo instance. body: {}", body));
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].text, "second");
+    }
+
+    #[tokio::test]
+    async fn should_upsert_new_todo() {
+        let (labels, label_ids) = label_fixture();
+        let expected = TodoEntity::new(1, "should_upsert_new_todo".to_string(), labels.clone());
+
+        let todo_repository = TodoRepositoryForMemory::new(labels);
+        let req = build_req_with_json(
+            "/todos/1",
+            Method::PUT,
+            r#"{ "text": "should_upsert_new_todo", "labels": [999] }"#.to_string(),
+        );
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        let todo = res_to_todo(res).await;
+        assert_eq!(expected, todo);
+    }
+
+    #[tokio::test]
+    async fn should_upsert_replace_existing_todo() {
+        let (labels, label_ids) = label_fixture();
+        let expected = TodoEntity::new(
+            1,
+            "should_upsert_replace_existing_todo".to_string(),
+            labels.clone(),
+        );
+
+        let todo_repository = TodoRepositoryForMemory::new(labels);
+        todo_repository
+            .create(CreateTodo::new(
+                "before_upsert".to_string(),
+                label_ids.clone(),
+            ))
+            .await
+            .expect("failed create todo");
+        let req = build_req_with_json(
+            "/todos/1",
+            Method::PUT,
+            r#"{ "text": "should_upsert_replace_existing_todo", "labels": [999] }"#.to_string(),
+        );
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        let todo = res_to_todo(res).await;
+        assert_eq!(expected, todo);
+    }
+
+    #[tokio::test]
+    async fn should_return_ok_health() {
+        let req = build_req_with_empty(Method::GET, "/health");
+        let res = create_app(
+            TodoRepositoryForMemory::new(vec![]),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    #[tokio::test]
+    async fn should_return_ok_health_db() {
+        let req = build_req_with_empty(Method::GET, "/health/db");
+        let res = create_app(
+            TodoRepositoryForMemory::new(vec![]),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, res.status());
+    }
+
+    #[tokio::test]
+    async fn should_return_unavailable_when_health_db_check_fails() {
+        let req = build_req_with_empty(Method::GET, "/health/db");
+        let res = create_app(
+            TodoRepositoryForMemory::new(vec![]),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new_failing(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, res.status());
+    }
+
+    #[tokio::test]
+    async fn should_stream_todo_events_on_create() {
+        use hyper::body::HttpBody;
+        use std::time::Duration;
+
+        let (labels, _label_ids) = label_fixture();
+        let app = create_app(
+            TodoRepositoryForMemory::new(labels),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        );
+
+        let events_req = build_req_with_empty(Method::GET, "/todos/events");
+        let mut events_res = app.clone().oneshot(events_req).await.unwrap();
+
+        let create_req = build_req_with_json(
+            "/todos",
+            Method::POST,
+            r#"{ "text": "should_stream_todo_events_on_create", "labels": [] }"#.to_string(),
+        );
+        app.oneshot(create_req).await.unwrap();
+
+        let chunk = tokio::time::timeout(Duration::from_secs(1), events_res.body_mut().data())
+            .await
+            .expect("timed out waiting for an SSE event")
+            .expect("stream ended before emitting an event")
+            .expect("failed reading SSE chunk");
+        let chunk = String::from_utf8(chunk.to_vec()).unwrap();
+        assert!(chunk.contains("should_stream_todo_events_on_create"));
+    }
+
+    #[tokio::test]
+    async fn should_batch_create_update_and_delete_todos() {
+        let (labels, label_ids) = label_fixture();
+        let todo_repository = TodoRepositoryForMemory::new(labels);
+        todo_repository
+            .create(CreateTodo::new("to_delete".to_string(), label_ids.clone()))
+            .await
+            .expect("failed create todo to delete");
+
+        let req = build_req_with_json(
+            "/todos/batch",
+            Method::POST,
+            r#"{
+                "create": [{ "text": "batch_created", "labels": [999] }],
+                "update": [{ "id": 1, "text": "batch_updated" }],
+                "delete": [1]
+            }"#
+            .to_string(),
+        );
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, res.status());
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let body: String = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(body.contains("batch_created"));
+        assert!(body.contains("batch_updated"));
+    }
+
+    #[tokio::test]
+    async fn should_report_batch_op_failure_without_affecting_others() {
+        let (labels, label_ids) = label_fixture();
+        let todo_repository = TodoRepositoryForMemory::new(labels);
+        todo_repository
+            .create(CreateTodo::new("existing".to_string(), label_ids.clone()))
+            .await
+            .expect("failed create existing todo");
+
+        let req = build_req_with_json(
+            "/todos/batch",
+            Method::POST,
+            r#"{
+                "create": [{ "text": "batch_created", "labels": [999] }],
+                "delete": [1, 999]
+            }"#
+            .to_string(),
+        );
+        let res = create_app(
+            todo_repository,
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::OK, res.status());
+        let bytes = hyper::body::to_bytes(res.into_body()).await.unwrap();
+        let body: String = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(body.contains("batch_created"));
+        assert!(body.contains("NotFound"));
+    }
+
+    #[tokio::test]
+    async fn should_reject_oversized_body() {
+        let (labels, _label_ids) = label_fixture();
+        let oversized_text = "x".repeat(DEFAULT_MAX_BODY_BYTES + 1);
+        let req = build_req_with_json(
+            "/todos",
+            Method::POST,
+            format!(r#"{{ "text": "{}", "labels": [] }}"#, oversized_text),
+        );
+        let res = create_app(
+            TodoRepositoryForMemory::new(labels),
+            LabelRepositoryForMemory::new(),
+            HealthCheckRepositoryForMemory::new(),
+        )
+        .oneshot(req)
+        .await
+        .unwrap();
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, res.status());
+    }
+
+    #[test]
+    fn should_default_max_body_bytes_when_env_unset() {
+        env::remove_var("MAX_BODY_BYTES");
+        assert_eq!(DEFAULT_MAX_BODY_BYTES, max_body_bytes());
+    }
+
+    #[test]
+    fn should_default_max_connections_when_env_unset() {
+        env::remove_var("DATABASE_MAX_CONNECTIONS");
+        assert!(max_connections() >= 1);
+    }
 }
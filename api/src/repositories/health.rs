@@ -0,0 +1,59 @@
+use axum::async_trait;
+use sqlx::PgPool;
+
+#[async_trait]
+pub trait HealthCheckRepository: Clone + std::marker::Send + std::marker::Sync + 'static {
+    async fn check(&self) -> anyhow::Result<()>;
+}
+
+#[derive(Debug, Clone)]
+pub struct HealthCheckRepositoryForDb {
+    pool: PgPool,
+}
+
+impl HealthCheckRepositoryForDb {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthCheckRepository for HealthCheckRepositoryForDb {
+    async fn check(&self) -> anyhow::Result<()> {
+        sqlx::query("select 1").fetch_one(&self.pool).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use axum::async_trait;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    pub struct HealthCheckRepositoryForMemory {
+        healthy: bool,
+    }
+
+    impl HealthCheckRepositoryForMemory {
+        pub fn new() -> Self {
+            Self { healthy: true }
+        }
+
+        pub fn new_failing() -> Self {
+            Self { healthy: false }
+        }
+    }
+
+    #[async_trait]
+    impl HealthCheckRepository for HealthCheckRepositoryForMemory {
+        async fn check(&self) -> anyhow::Result<()> {
+            if self.healthy {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("database unreachable"))
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub mod health;
+pub mod label;
+pub mod todo;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RepositoryError {
+    #[error("NotFound, id is {0}")]
+    NotFound(i32),
+    #[error("Duplicate, id is {0}")]
+    Duplicate(i32),
+    #[error("Unexpected Error: [{0}]")]
+    Unexpected(String),
+}
@@ -0,0 +1,808 @@
+use super::label::Label;
+use super::RepositoryError;
+use anyhow::Ok;
+use axum::async_trait;
+use serde::{Deserialize, Serialize};
+use sqlx::{FromRow, PgPool};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[async_trait]
+pub trait TodoRepository: Clone + std::marker::Send + std::marker::Sync + 'static {
+    async fn create(&self, payload: CreateTodo) -> anyhow::Result<TodoEntity>;
+    async fn find(&self, id: i32) -> anyhow::Result<TodoEntity>;
+    async fn all(&self, opts: ListOptions) -> anyhow::Result<Vec<TodoEntity>>;
+    async fn update(&self, id: i32, payload: UpdateTodo) -> anyhow::Result<TodoEntity>;
+    async fn upsert(&self, id: i32, payload: CreateTodo) -> anyhow::Result<UpsertOutcome>;
+    async fn delete(&self, id: i32) -> anyhow::Result<()>;
+    async fn batch(&self, ops: BatchOps) -> anyhow::Result<BatchResults>;
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TodoEntity {
+    pub id: i32,
+    pub text: String,
+    pub completed: bool,
+    pub labels: Vec<Label>,
+}
+
+impl TodoEntity {
+    pub fn new(id: i32, text: String, labels: Vec<Label>) -> Self {
+        Self {
+            id,
+            text,
+            completed: false,
+            labels,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct CreateTodo {
+    text: String,
+    labels: Vec<i32>,
+}
+
+impl CreateTodo {
+    pub fn new(text: String, labels: Vec<i32>) -> Self {
+        Self { text, labels }
+    }
+}
+
+/// Result of `TodoRepository::upsert`, telling the caller whether the id
+/// was newly created or an existing todo was replaced, so callers (e.g. the
+/// SSE publisher) don't need a separate pre-check that could race the
+/// actual write.
+#[derive(Debug, Clone)]
+pub enum UpsertOutcome {
+    Created(TodoEntity),
+    Updated(TodoEntity),
+}
+
+impl UpsertOutcome {
+    pub fn into_entity(self) -> TodoEntity {
+        match self {
+            UpsertOutcome::Created(todo) | UpsertOutcome::Updated(todo) => todo,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+pub struct UpdateTodo {
+    pub id: i32,
+    text: Option<String>,
+    completed: Option<bool>,
+    labels: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TodoEventKind {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// Published on the `/todos/events` broadcast channel after a mutating
+/// repository call succeeds, so SSE subscribers can react without polling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TodoEvent {
+    pub kind: TodoEventKind,
+    pub todo: Option<TodoEntity>,
+    pub id: i32,
+}
+
+/// Request body for `POST /todos/batch`. Each op is applied independently
+/// (via its own savepoint on the DB backend) and reports its own success or
+/// failure — one op failing does not undo any other op in the batch.
+#[derive(Debug, Deserialize, Default)]
+pub struct BatchOps {
+    #[serde(default)]
+    pub create: Vec<CreateTodo>,
+    #[serde(default)]
+    pub delete: Vec<i32>,
+    #[serde(default)]
+    pub update: Vec<UpdateTodo>,
+}
+
+#[derive(Debug, Serialize)]
+pub enum BatchItemResult<T> {
+    Ok(T),
+    Err(String),
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchResults {
+    pub created: Vec<BatchItemResult<TodoEntity>>,
+    pub updated: Vec<BatchItemResult<TodoEntity>>,
+    pub deleted: Vec<BatchItemResult<i32>>,
+}
+
+/// Query parameters for `GET /todos`. `limit` defaults to `DEFAULT_LIMIT` and
+/// is capped at `MAX_LIMIT` so a client can't force an unbounded scan.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Default)]
+pub struct ListOptions {
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+    pub label_id: Option<i32>,
+}
+
+impl ListOptions {
+    fn limit(&self) -> i64 {
+        self.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+    }
+
+    fn offset(&self) -> i64 {
+        self.offset.unwrap_or(0).max(0)
+    }
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct TodoFromRow {
+    id: i32,
+    text: String,
+    completed: bool,
+}
+
+#[derive(Debug, Clone, FromRow)]
+struct TodoWithLabelFromRow {
+    id: i32,
+    text: String,
+    completed: bool,
+    label_id: Option<i32>,
+    label_name: Option<String>,
+}
+
+fn fold_entities(rows: Vec<TodoWithLabelFromRow>) -> Vec<TodoEntity> {
+    let mut accum: Vec<TodoEntity> = vec![];
+    for row in rows {
+        if let Some(todo) = accum.iter_mut().find(|todo| todo.id == row.id) {
+            if let (Some(label_id), Some(label_name)) = (row.label_id, row.label_name) {
+                todo.labels.push(Label::new(label_id, label_name));
+            }
+            continue;
+        }
+
+        let labels = match (row.label_id, row.label_name) {
+            (Some(label_id), Some(label_name)) => vec![Label::new(label_id, label_name)],
+            _ => vec![],
+        };
+
+        accum.push(TodoEntity {
+            id: row.id,
+            text: row.text,
+            completed: row.completed,
+            labels,
+        });
+    }
+    accum
+}
+
+#[derive(Debug, Clone)]
+pub struct TodoRepositoryForDb {
+    pool: PgPool,
+}
+
+impl TodoRepositoryForDb {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TodoRepository for TodoRepositoryForDb {
+    async fn create(&self, payload: CreateTodo) -> anyhow::Result<TodoEntity> {
+        let row = sqlx::query_as::<_, TodoFromRow>(
+            r#"
+                insert into todos (text, completed) values ($1, false) returning *
+            "#,
+        )
+        .bind(payload.text.clone())
+        .fetch_one(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+                insert into todo_labels (todo_id, label_id)
+                select $1, id from unnest($2) as t(id)
+            "#,
+        )
+        .bind(row.id)
+        .bind(&payload.labels)
+        .execute(&self.pool)
+        .await?;
+
+        self.find(row.id).await
+    }
+
+    async fn find(&self, id: i32) -> anyhow::Result<TodoEntity> {
+        let rows = sqlx::query_as::<_, TodoWithLabelFromRow>(
+            r#"
+                select todos.*, labels.id as label_id, labels.name as label_name
+                from todos
+                left join todo_labels on todos.id = todo_labels.todo_id
+                left join labels on labels.id = todo_labels.label_id
+                where todos.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let todo = fold_entities(rows)
+            .into_iter()
+            .next()
+            .ok_or(RepositoryError::NotFound(id))?;
+
+        Ok(todo)
+    }
+
+    async fn all(&self, opts: ListOptions) -> anyhow::Result<Vec<TodoEntity>> {
+        let rows = sqlx::query_as::<_, TodoWithLabelFromRow>(
+            r#"
+                select todos.*, labels.id as label_id, labels.name as label_name
+                from todos
+                left join todo_labels on todos.id = todo_labels.todo_id
+                left join labels on labels.id = todo_labels.label_id
+                where (
+                    $1::int is null
+                    or todos.id in (select todo_id from todo_labels where label_id = $1)
+                )
+                and todos.id in (
+                    select id from todos
+                    where $1::int is null or id in (select todo_id from todo_labels where label_id = $1)
+                    order by id asc
+                    limit $2 offset $3
+                )
+                order by todos.id asc
+            "#,
+        )
+        .bind(opts.label_id)
+        .bind(opts.limit())
+        .bind(opts.offset())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(fold_entities(rows))
+    }
+
+    async fn update(&self, id: i32, payload: UpdateTodo) -> anyhow::Result<TodoEntity> {
+        let current = self.find(id).await?;
+
+        sqlx::query(
+            r#"
+                update todos set text = $1, completed = $2 where id = $3
+            "#,
+        )
+        .bind(payload.text.unwrap_or(current.text))
+        .bind(payload.completed.unwrap_or(current.completed))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if let Some(labels) = payload.labels {
+            sqlx::query(r#"delete from todo_labels where todo_id = $1"#)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+
+            sqlx::query(
+                r#"
+                    insert into todo_labels (todo_id, label_id)
+                    select $1, id from unnest($2) as t(id)
+                "#,
+            )
+            .bind(id)
+            .bind(&labels)
+            .execute(&self.pool)
+            .await?;
+        }
+
+        self.find(id).await
+    }
+
+    async fn upsert(&self, id: i32, payload: CreateTodo) -> anyhow::Result<UpsertOutcome> {
+        let mut tx = self.pool.begin().await?;
+
+        // `xmax = 0` is true only for a row's initial insert, not for a row
+        // touched by the `on conflict` update, so this tells us whether we
+        // created or replaced without a separate (racy) existence check.
+        let (inserted,): (bool,) = sqlx::query_as(
+            r#"
+                insert into todos (id, text, completed) values ($1, $2, false)
+                on conflict (id) do update set text = excluded.text
+                returning (xmax = 0) as inserted
+            "#,
+        )
+        .bind(id)
+        .bind(payload.text.clone())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(r#"delete from todo_labels where todo_id = $1"#)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        sqlx::query(
+            r#"
+                insert into todo_labels (todo_id, label_id)
+                select $1, id from unnest($2) as t(id)
+            "#,
+        )
+        .bind(id)
+        .bind(&payload.labels)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+
+        let todo = self.find(id).await?;
+        Ok(if inserted {
+            UpsertOutcome::Created(todo)
+        } else {
+            UpsertOutcome::Updated(todo)
+        })
+    }
+
+    async fn delete(&self, id: i32) -> anyhow::Result<()> {
+        sqlx::query(r#"delete from todo_labels where todo_id = $1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(r#"delete from todos where id = $1"#)
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => RepositoryError::NotFound(id),
+                _ => RepositoryError::Unexpected(e.to_string()),
+            })?;
+
+        Ok(())
+    }
+
+    async fn batch(&self, ops: BatchOps) -> anyhow::Result<BatchResults> {
+        let mut tx = self.pool.begin().await?;
+
+        // Each op runs in its own savepoint so one failure (e.g. a duplicate
+        // label id) can't poison the outer transaction and cascade bogus
+        // "current transaction is aborted" errors onto every later op. A
+        // failed op's savepoint is rolled back on its own, but ops that
+        // already succeeded keep their writes — batch semantics are
+        // per-op, not all-or-nothing, matching TodoRepositoryForMemory.
+        let mut created = Vec::with_capacity(ops.create.len());
+        for payload in ops.create {
+            let mut savepoint = tx.begin().await?;
+            match Self::create_in_tx(&mut savepoint, payload).await {
+                Result::Ok(todo) => {
+                    savepoint.commit().await?;
+                    created.push(BatchItemResult::Ok(todo));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    created.push(BatchItemResult::Err(e.to_string()));
+                }
+            }
+        }
+
+        let mut updated = Vec::with_capacity(ops.update.len());
+        for payload in ops.update {
+            let id = payload.id;
+            let mut savepoint = tx.begin().await?;
+            match Self::update_in_tx(&mut savepoint, id, payload).await {
+                Result::Ok(todo) => {
+                    savepoint.commit().await?;
+                    updated.push(BatchItemResult::Ok(todo));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    updated.push(BatchItemResult::Err(e.to_string()));
+                }
+            }
+        }
+
+        let mut deleted = Vec::with_capacity(ops.delete.len());
+        for id in ops.delete {
+            let mut savepoint = tx.begin().await?;
+            match Self::delete_in_tx(&mut savepoint, id).await {
+                Result::Ok(()) => {
+                    savepoint.commit().await?;
+                    deleted.push(BatchItemResult::Ok(id));
+                }
+                Err(e) => {
+                    savepoint.rollback().await?;
+                    deleted.push(BatchItemResult::Err(e.to_string()));
+                }
+            }
+        }
+
+        tx.commit().await?;
+
+        Ok(BatchResults {
+            created,
+            updated,
+            deleted,
+        })
+    }
+}
+
+impl TodoRepositoryForDb {
+    async fn find_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: i32,
+    ) -> anyhow::Result<TodoEntity> {
+        let rows = sqlx::query_as::<_, TodoWithLabelFromRow>(
+            r#"
+                select todos.*, labels.id as label_id, labels.name as label_name
+                from todos
+                left join todo_labels on todos.id = todo_labels.todo_id
+                left join labels on labels.id = todo_labels.label_id
+                where todos.id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut **tx)
+        .await?;
+
+        fold_entities(rows)
+            .into_iter()
+            .next()
+            .ok_or_else(|| RepositoryError::NotFound(id).into())
+    }
+
+    async fn create_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        payload: CreateTodo,
+    ) -> anyhow::Result<TodoEntity> {
+        let row = sqlx::query_as::<_, TodoFromRow>(
+            r#"
+                insert into todos (text, completed) values ($1, false) returning *
+            "#,
+        )
+        .bind(payload.text.clone())
+        .fetch_one(&mut **tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+                insert into todo_labels (todo_id, label_id)
+                select $1, id from unnest($2) as t(id)
+            "#,
+        )
+        .bind(row.id)
+        .bind(&payload.labels)
+        .execute(&mut **tx)
+        .await?;
+
+        Self::find_in_tx(tx, row.id).await
+    }
+
+    async fn update_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: i32,
+        payload: UpdateTodo,
+    ) -> anyhow::Result<TodoEntity> {
+        let current = Self::find_in_tx(tx, id).await?;
+
+        sqlx::query(
+            r#"
+                update todos set text = $1, completed = $2 where id = $3
+            "#,
+        )
+        .bind(payload.text.unwrap_or(current.text))
+        .bind(payload.completed.unwrap_or(current.completed))
+        .bind(id)
+        .execute(&mut **tx)
+        .await?;
+
+        if let Some(labels) = payload.labels {
+            sqlx::query(r#"delete from todo_labels where todo_id = $1"#)
+                .bind(id)
+                .execute(&mut **tx)
+                .await?;
+
+            sqlx::query(
+                r#"
+                    insert into todo_labels (todo_id, label_id)
+                    select $1, id from unnest($2) as t(id)
+                "#,
+            )
+            .bind(id)
+            .bind(&labels)
+            .execute(&mut **tx)
+            .await?;
+        }
+
+        Self::find_in_tx(tx, id).await
+    }
+
+    async fn delete_in_tx(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        id: i32,
+    ) -> anyhow::Result<()> {
+        sqlx::query(r#"delete from todo_labels where todo_id = $1"#)
+            .bind(id)
+            .execute(&mut **tx)
+            .await?;
+
+        sqlx::query(r#"delete from todos where id = $1"#)
+            .bind(id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => RepositoryError::NotFound(id),
+                _ => RepositoryError::Unexpected(e.to_string()),
+            })?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "database-test")]
+mod test {
+    use std::env;
+
+    use dotenv::dotenv;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn crud_scenario() {
+        dotenv().ok();
+        let database_url = &env::var("DATABASE_URL").expect("undefined [DATABASE_URL]");
+        let pool = PgPool::connect(database_url)
+            .await
+            .unwrap_or_else(|_| panic!("failed connect database. utl: {}", database_url));
+
+        let repository = TodoRepositoryForDb::new(pool.clone());
+        let todo_text = "[crud_scenario] text";
+
+        //create
+        let todo = repository
+            .create(CreateTodo::new(todo_text.to_string(), vec![]))
+            .await
+            .expect("[create] returned Err");
+        assert_eq!(todo.text, todo_text);
+
+        //find
+        let todo = repository
+            .find(todo.id)
+            .await
+            .expect("[find] returned Err");
+        assert_eq!(todo.text, todo_text);
+
+        //all
+        let todos = repository
+            .all(ListOptions::default())
+            .await
+            .expect("[all] returned Err");
+        let todo = todos.last().unwrap();
+        assert_eq!(todo.text, todo_text);
+
+        //update
+        let updated_text = "[crud_scenario] updated text";
+        let todo = repository
+            .update(
+                todo.id,
+                UpdateTodo {
+                    id: todo.id,
+                    text: Some(updated_text.to_string()),
+                    completed: Some(true),
+                    labels: None,
+                },
+            )
+            .await
+            .expect("[update] returned Err");
+        assert_eq!(todo.text, updated_text);
+        assert!(todo.completed);
+
+        //delete
+        repository
+            .delete(todo.id)
+            .await
+            .expect("[delete] returned Err");
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use axum::async_trait;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    };
+
+    use super::*;
+
+    type TodoDatas = HashMap<i32, TodoEntity>;
+
+    #[derive(Debug, Clone)]
+    pub struct TodoRepositoryForMemory {
+        store: Arc<RwLock<TodoDatas>>,
+        labels: Vec<Label>,
+    }
+
+    impl TodoRepositoryForMemory {
+        pub fn new(labels: Vec<Label>) -> Self {
+            TodoRepositoryForMemory {
+                store: Arc::default(),
+                labels,
+            }
+        }
+
+        fn write_store_ref(&self) -> RwLockWriteGuard<TodoDatas> {
+            self.store.write().unwrap()
+        }
+
+        fn read_store_ref(&self) -> RwLockReadGuard<TodoDatas> {
+            self.store.read().unwrap()
+        }
+
+        fn resolve_labels(&self, ids: &[i32]) -> Vec<Label> {
+            self.labels
+                .iter()
+                .filter(|label| ids.contains(&label.id))
+                .cloned()
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl TodoRepository for TodoRepositoryForMemory {
+        async fn create(&self, payload: CreateTodo) -> anyhow::Result<TodoEntity> {
+            let mut store = self.write_store_ref();
+            let id = store.len() as i32 + 1;
+            let labels = self.resolve_labels(&payload.labels);
+            let todo = TodoEntity::new(id, payload.text.clone(), labels);
+            store.insert(id, todo.clone());
+            Ok(todo)
+        }
+
+        async fn find(&self, id: i32) -> anyhow::Result<TodoEntity> {
+            let store = self.read_store_ref();
+            let todo = store.get(&id).ok_or(RepositoryError::NotFound(id))?;
+            Ok(todo.clone())
+        }
+
+        async fn all(&self, opts: ListOptions) -> anyhow::Result<Vec<TodoEntity>> {
+            let store = self.read_store_ref();
+            let mut todos: Vec<TodoEntity> = store
+                .values()
+                .filter(|todo| match opts.label_id {
+                    Some(label_id) => todo.labels.iter().any(|label| label.id == label_id),
+                    None => true,
+                })
+                .cloned()
+                .collect();
+            todos.sort_by_key(|todo| todo.id);
+
+            let offset = opts.offset() as usize;
+            let limit = opts.limit() as usize;
+            Ok(todos.into_iter().skip(offset).take(limit).collect())
+        }
+
+        async fn update(&self, id: i32, payload: UpdateTodo) -> anyhow::Result<TodoEntity> {
+            let mut store = self.write_store_ref();
+            let todo = store.get(&id).ok_or(RepositoryError::NotFound(id))?;
+            let text = payload.text.unwrap_or_else(|| todo.text.clone());
+            let completed = payload.completed.unwrap_or(todo.completed);
+            let labels = match payload.labels {
+                Some(ids) => self.resolve_labels(&ids),
+                None => todo.labels.clone(),
+            };
+            let todo = TodoEntity {
+                id,
+                text,
+                completed,
+                labels,
+            };
+            store.insert(id, todo.clone());
+            Ok(todo)
+        }
+
+        async fn upsert(&self, id: i32, payload: CreateTodo) -> anyhow::Result<UpsertOutcome> {
+            let mut store = self.write_store_ref();
+            let labels = self.resolve_labels(&payload.labels);
+            let todo = TodoEntity::new(id, payload.text.clone(), labels);
+            let existed = store.insert(id, todo.clone()).is_some();
+            Ok(if existed {
+                UpsertOutcome::Updated(todo)
+            } else {
+                UpsertOutcome::Created(todo)
+            })
+        }
+
+        async fn delete(&self, id: i32) -> anyhow::Result<()> {
+            let mut store = self.write_store_ref();
+            store.remove(&id).ok_or(RepositoryError::NotFound(id))?;
+            Ok(())
+        }
+
+        async fn batch(&self, ops: BatchOps) -> anyhow::Result<BatchResults> {
+            let mut created = Vec::with_capacity(ops.create.len());
+            for payload in ops.create {
+                match self.create(payload).await {
+                    Result::Ok(todo) => created.push(BatchItemResult::Ok(todo)),
+                    Err(e) => created.push(BatchItemResult::Err(e.to_string())),
+                }
+            }
+
+            let mut updated = Vec::with_capacity(ops.update.len());
+            for payload in ops.update {
+                let id = payload.id;
+                match self.update(id, payload).await {
+                    Result::Ok(todo) => updated.push(BatchItemResult::Ok(todo)),
+                    Err(e) => updated.push(BatchItemResult::Err(e.to_string())),
+                }
+            }
+
+            let mut deleted = Vec::with_capacity(ops.delete.len());
+            for id in ops.delete {
+                match self.delete(id).await {
+                    Result::Ok(()) => deleted.push(BatchItemResult::Ok(id)),
+                    Err(e) => deleted.push(BatchItemResult::Err(e.to_string())),
+                }
+            }
+
+            Ok(BatchResults {
+                created,
+                updated,
+                deleted,
+            })
+        }
+    }
+
+    mod test {
+        use super::*;
+
+        #[tokio::test]
+        async fn todo_crud_scenario() {
+            let text = "todo text".to_string();
+            let id = 1;
+            let expected = TodoEntity::new(id, text.clone(), vec![]);
+
+            //create
+            let repository = TodoRepositoryForMemory::new(vec![]);
+            let todo = repository
+                .create(CreateTodo::new(text.clone(), vec![]))
+                .await
+                .expect("failed create todo");
+            assert_eq!(expected, todo);
+
+            //find
+            let todo = repository.find(id).await.expect("failed find todo");
+            assert_eq!(expected, todo);
+
+            //all
+            let todos = repository
+                .all(ListOptions::default())
+                .await
+                .expect("failed get all todo");
+            assert_eq!(vec![expected], todos);
+
+            //update
+            let updated_text = "updated text".to_string();
+            let todo = repository
+                .update(
+                    id,
+                    UpdateTodo {
+                        id,
+                        text: Some(updated_text.clone()),
+                        completed: Some(true),
+                        labels: None,
+                    },
+                )
+                .await
+                .expect("failed update todo");
+            assert_eq!(todo.text, updated_text);
+            assert!(todo.completed);
+
+            //delete
+            let res = repository.delete(id).await;
+            assert!(res.is_ok())
+        }
+    }
+}